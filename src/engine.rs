@@ -0,0 +1,149 @@
+use pleco::{Board, BitMove, Player, PieceType, SQ as Square};
+
+/// Score assigned to a checkmate. Kept well below `i32::MAX` so that
+/// `-MATE + ply` mate adjustments never overflow.
+const MATE: i32 = 1_000_000;
+
+/// Material value of each piece type, in centipawns.
+fn piece_value(pt: PieceType) -> i32 {
+    match pt {
+        PieceType::P => 100,
+        PieceType::N => 320,
+        PieceType::B => 330,
+        PieceType::R => 500,
+        PieceType::Q => 900,
+        _ => 0,
+    }
+}
+
+/// Piece-square bonus tables, indexed from White's point of view with square 0
+/// at a1. They nudge the search toward sensible development without needing a
+/// full evaluation function.
+const PAWN_PST: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10,-20,-20, 10, 10,  5,
+     5, -5,-10,  0,  0,-10, -5,  5,
+     0,  0,  0, 20, 20,  0,  0,  0,
+     5,  5, 10, 25, 25, 10,  5,  5,
+    10, 10, 20, 30, 30, 20, 10, 10,
+    50, 50, 50, 50, 50, 50, 50, 50,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+const KNIGHT_PST: [i32; 64] = [
+    -50,-40,-30,-30,-30,-30,-40,-50,
+    -40,-20,  0,  5,  5,  0,-20,-40,
+    -30,  5, 10, 15, 15, 10,  5,-30,
+    -30,  0, 15, 20, 20, 15,  0,-30,
+    -30,  5, 15, 20, 20, 15,  5,-30,
+    -30,  0, 10, 15, 15, 10,  0,-30,
+    -40,-20,  0,  0,  0,  0,-20,-40,
+    -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+const BISHOP_PST: [i32; 64] = [
+    -20,-10,-10,-10,-10,-10,-10,-20,
+    -10,  5,  0,  0,  0,  0,  5,-10,
+    -10, 10, 10, 10, 10, 10, 10,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10,  5,  5, 10, 10,  5,  5,-10,
+    -10,  0,  5, 10, 10,  5,  0,-10,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+fn pst_value(pt: PieceType, sq: Square, player: Player) -> i32 {
+    let idx = match player {
+        // White reads the table directly; Black mirrors it vertically.
+        Player::White => sq.0 as usize,
+        Player::Black => (sq.0 ^ 56) as usize,
+    };
+    match pt {
+        PieceType::P => PAWN_PST[idx],
+        PieceType::N => KNIGHT_PST[idx],
+        PieceType::B => BISHOP_PST[idx],
+        _ => 0,
+    }
+}
+
+/// Static evaluation from the side-to-move's perspective. Sums material and
+/// piece-square bonuses as white-minus-black, then negates for Black.
+fn evaluate(board: &Board) -> i32 {
+    let mut score = 0i32;
+    for sq in 0..64u8 {
+        let sq = Square(sq);
+        let piece = board.piece_at_sq(sq);
+        let pt = piece.type_of();
+        if pt == PieceType::None {
+            continue;
+        }
+        if let Some(player) = piece.player() {
+            let val = piece_value(pt) + pst_value(pt, sq, player);
+            match player {
+                Player::White => score += val,
+                Player::Black => score -= val,
+            }
+        }
+    }
+    if board.turn() == Player::White {
+        score
+    } else {
+        -score
+    }
+}
+
+/// Negamax search with alpha-beta pruning. Returns a score from the
+/// side-to-move's perspective; `ply` tracks distance from the root so that
+/// shorter mates score higher.
+fn negamax(board: &mut Board, depth: u32, ply: u32, mut alpha: i32, beta: i32) -> i32 {
+    if depth == 0 {
+        return evaluate(board);
+    }
+
+    let moves = board.generate_moves();
+    if moves.is_empty() {
+        // No legal moves: checkmate if in check, otherwise stalemate.
+        return if board.in_check() {
+            -MATE + ply as i32
+        } else {
+            0
+        };
+    }
+
+    for mv in moves.iter() {
+        board.apply_move(*mv);
+        let score = -negamax(board, depth - 1, ply + 1, -beta, -alpha);
+        board.undo_move();
+        if score >= beta {
+            return beta;
+        }
+        if score > alpha {
+            alpha = score;
+        }
+    }
+    alpha
+}
+
+/// Pick the best move for the side to move by searching `depth` plies ahead.
+/// Returns `None` when there are no legal moves.
+pub fn best_move(board: &Board, depth: u32) -> Option<BitMove> {
+    let mut board = board.shallow_clone();
+    let moves = board.generate_moves();
+    if moves.is_empty() {
+        return None;
+    }
+
+    let mut best = None;
+    let mut alpha = -MATE - 1;
+    let beta = MATE + 1;
+    for mv in moves.iter() {
+        board.apply_move(*mv);
+        let score = -negamax(&mut board, depth.saturating_sub(1), 1, -beta, -alpha);
+        board.undo_move();
+        if score > alpha {
+            alpha = score;
+            best = Some(*mv);
+        }
+    }
+    best
+}
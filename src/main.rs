@@ -1,6 +1,6 @@
 use std::{io, time::Duration};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -10,37 +10,120 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Terminal,
 };
-use pleco::{Board, Player, Piece as PlecoPrec, Rank, File, SQ as Square, MoveList, Piece};
-use rand::prelude::*;
+use pleco::{Board, BitMove, Player, Piece as PlecoPrec, PieceType, Rank, File, SQ as Square, Piece};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 
+mod engine;
+
+/// Default search depth for the AI. Higher is stronger but slower.
+const DEFAULT_SEARCH_DEPTH: u32 = 3;
+
+/// Interaction mode the app is currently in. `Normal` handles cursor and move
+/// input; `FenInput` collects a FEN string typed into the status line.
+enum Mode {
+    Normal,
+    FenInput(String),
+    /// A pawn is promoting from `src` to `dest`; awaiting the piece choice.
+    Promotion { src: Square, dest: Square },
+}
+
 struct App {
     board: Board,
     cursor_pos: Square,
     selected_pos: Option<Square>,
     message: String,
+    search_depth: u32,
+    mode: Mode,
+    move_history: Vec<BitMove>,
+    redo_stack: Vec<BitMove>,
+    zobrist_history: Vec<u64>,
 }
 
 impl App {
     fn new() -> Self {
+        let board = Board::start_pos();
+        let zobrist_history = vec![board.zobrist()];
         App {
-            board: Board::start_pos(),
+            board,
             cursor_pos: Square::make(File::A, Rank::R1),
             selected_pos: None,
             message: String::new(),
+            search_depth: DEFAULT_SEARCH_DEPTH,
+            mode: Mode::Normal,
+            move_history: Vec::new(),
+            redo_stack: Vec::new(),
+            zobrist_history,
+        }
+    }
+
+    /// Reset all game history to match `self.board` — used after loading a FEN.
+    fn reset_history(&mut self) {
+        self.move_history.clear();
+        self.redo_stack.clear();
+        self.zobrist_history = vec![self.board.zobrist()];
+    }
+
+    /// Apply a move and record it in the history. Playing a new move discards
+    /// any redo stack.
+    fn push_move(&mut self, mv: BitMove) {
+        self.board.apply_move(mv);
+        self.move_history.push(mv);
+        self.zobrist_history.push(self.board.zobrist());
+        self.redo_stack.clear();
+    }
+
+    /// Undo the most recent full turn — both the AI reply and the player move.
+    fn undo(&mut self) {
+        if self.move_history.is_empty() {
+            self.message = "Nothing to undo".to_string();
+            return;
+        }
+        // Undo up to two plies so the player regains the move. The player
+        // moves first from every starting position, so an odd-length history
+        // ends with a player move that had no AI reply: undo just that ply.
+        let plies = if self.move_history.len() % 2 == 1 { 1 } else { 2 };
+        for _ in 0..plies {
+            if let Some(mv) = self.move_history.pop() {
+                self.board.undo_move();
+                self.zobrist_history.pop();
+                self.redo_stack.push(mv);
+            } else {
+                break;
+            }
+        }
+        self.selected_pos = None;
+        self.message = "Move undone".to_string();
+    }
+
+    /// Redo a previously undone turn, replaying the plies in order.
+    fn redo(&mut self) {
+        if self.redo_stack.is_empty() {
+            self.message = "Nothing to redo".to_string();
+            return;
         }
+        for _ in 0..2 {
+            if let Some(mv) = self.redo_stack.pop() {
+                self.board.apply_move(mv);
+                self.move_history.push(mv);
+                self.zobrist_history.push(self.board.zobrist());
+            } else {
+                break;
+            }
+        }
+        self.selected_pos = None;
+        self.message = "Move redone".to_string();
     }
 
     fn make_ai_move(&mut self) {
-        let moves: MoveList = self.board.generate_moves();
-        if !moves.is_empty() {
-            let mut rng = thread_rng();
-            let chosen_move = moves[rng.gen_range(0..moves.len())];
-            self.board.apply_move(chosen_move);
-            self.message = format!("AI moved: {}", chosen_move);
-        } else {
-            self.message = "No legal moves available".to_string();
+        match engine::best_move(&self.board, self.search_depth) {
+            Some(chosen_move) => {
+                self.push_move(chosen_move);
+                self.message = format!("AI moved: {}", chosen_move);
+            }
+            None => {
+                self.message = "No legal moves available".to_string();
+            }
         }
     }
 
@@ -72,7 +155,15 @@ impl App {
                     if let Some(mv) = legal_moves.iter().find(|mv|
                         mv.get_src() == selected && mv.get_dest() == self.cursor_pos
                     ) {
-                        self.board.apply_move(*mv);
+                        if mv.is_promo() {
+                            // Defer to the promotion dialog so the player can
+                            // choose which piece to promote to.
+                            self.selected_pos = None;
+                            self.mode = Mode::Promotion { src: selected, dest: self.cursor_pos };
+                            self.message = "Promote to (q/r/b/n):".to_string();
+                            return;
+                        }
+                        self.push_move(*mv);
                         self.selected_pos = None;
                         self.message = format!("Moved: {}", mv.to_string());
 
@@ -95,8 +186,134 @@ impl App {
                 self.selected_pos = None;
                 self.message = "Selection cleared".to_string();
             }
+            KeyCode::Char('d') => {
+                // Cycle the AI difficulty through depths 1..=4.
+                self.search_depth = self.search_depth % 4 + 1;
+                self.message = format!("AI difficulty: depth {}", self.search_depth);
+            }
+            KeyCode::Char('f') => {
+                // Open an input line to paste a FEN string.
+                self.selected_pos = None;
+                self.mode = Mode::FenInput(String::new());
+                self.message = "FEN> ".to_string();
+            }
+            KeyCode::Char('e') => {
+                // Export the current position's FEN to the status pane.
+                self.message = self.board.fen();
+            }
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Char('r') => self.redo(),
+            _ => {}
+        }
+    }
+
+    /// Handle a key while collecting a FEN string. Enter loads the position,
+    /// Esc cancels, and printable characters extend the buffer.
+    fn on_fen_key(&mut self, key: KeyCode) {
+        let buf = match &mut self.mode {
+            Mode::FenInput(buf) => buf,
+            _ => return,
+        };
+        match key {
+            KeyCode::Char(c) => buf.push(c),
+            KeyCode::Backspace => {
+                buf.pop();
+            }
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.message = "FEN input cancelled".to_string();
+                return;
+            }
+            KeyCode::Enter => {
+                let fen = buf.trim().to_string();
+                match Board::from_fen(&fen) {
+                    Ok(board) => {
+                        self.board = board;
+                        self.selected_pos = None;
+                        self.reset_history();
+                        self.message = "Position loaded".to_string();
+                    }
+                    Err(err) => {
+                        self.message = format!("Invalid FEN: {:?}", err);
+                    }
+                }
+                self.mode = Mode::Normal;
+                return;
+            }
             _ => {}
         }
+        if let Mode::FenInput(buf) = &self.mode {
+            self.message = format!("FEN> {}", buf);
+        }
+    }
+
+    /// Handle the piece choice in the promotion dialog, applying the specific
+    /// promotion move that matches the chosen piece.
+    fn on_promotion_key(&mut self, key: KeyCode) {
+        let (src, dest) = match self.mode {
+            Mode::Promotion { src, dest } => (src, dest),
+            _ => return,
+        };
+        let promo = match key {
+            KeyCode::Char('q') => PieceType::Q,
+            KeyCode::Char('r') => PieceType::R,
+            KeyCode::Char('b') => PieceType::B,
+            KeyCode::Char('n') => PieceType::N,
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.message = "Promotion cancelled".to_string();
+                return;
+            }
+            _ => return,
+        };
+
+        let mv = self.board.generate_moves().iter().find(|mv|
+            mv.get_src() == src && mv.get_dest() == dest
+                && mv.is_promo() && mv.promo_piece() == promo
+        ).copied();
+
+        self.mode = Mode::Normal;
+        if let Some(mv) = mv {
+            self.push_move(mv);
+            self.message = format!("Moved: {}", mv.to_string());
+            if !self.board.checkmate() && !self.board.stalemate() {
+                self.make_ai_move();
+            }
+        } else {
+            self.message = "Invalid move!".to_string();
+        }
+    }
+
+    /// Translate a terminal click at `(col, row)` into a board `Square`, using
+    /// the same geometry as the render: a one-cell border, a header row, a
+    /// 2-char rank label, and 3-char-wide cells. Returns `None` for clicks
+    /// outside the grid.
+    fn square_at(&self, col: u16, row: u16) -> Option<Square> {
+        // Border (1) + header row (1) precede the first rank's row.
+        if row < 2 || col < 3 {
+            return None;
+        }
+        let board_row = row - 2;
+        if board_row > 7 {
+            return None;
+        }
+        // Ranks are drawn from 8 down to 1, so the top row is rank index 7.
+        let rank_idx = 7 - board_row as u8;
+        // Border (1) + rank label (2) precede the first cell; cells are 3 wide.
+        let file = (col - 3) / 3;
+        if file > 7 {
+            return None;
+        }
+        Some(Square(rank_idx * 8 + file as u8))
+    }
+
+    /// Handle a left click: move the cursor to the clicked square and run the
+    /// same select/move logic as the Enter key.
+    fn on_click(&mut self, col: u16, row: u16) {
+        if let Some(sq) = self.square_at(col, row) {
+            self.cursor_pos = sq;
+            self.on_key(KeyCode::Enter);
+        }
     }
 
     fn get_piece_char(piece: Piece) -> char {
@@ -146,11 +363,22 @@ impl App {
         style
     }
 
+    /// Returns true when the current position has occurred three times in the
+    /// game history, per the threefold-repetition draw rule.
+    fn is_threefold_repetition(&self) -> bool {
+        let current = self.board.zobrist();
+        self.zobrist_history.iter().filter(|&&key| key == current).count() >= 3
+    }
+
     fn get_game_status(&self) -> String {
         if self.board.checkmate() {
             format!("Checkmate! {} wins!", if self.board.turn() == Player::White { "Black" } else { "White" })
         } else if self.board.stalemate() {
             "Stalemate!".to_string()
+        } else if self.is_threefold_repetition() {
+            "Draw by threefold repetition!".to_string()
+        } else if self.board.rule_50() >= 100 {
+            "Draw by fifty-move rule!".to_string()
         } else if self.board.in_check() {
             format!("{} is in check!", if self.board.turn() == Player::White { "White" } else { "Black" })
         } else {
@@ -231,18 +459,46 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
         })?;
 
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') {
-                    break;
+            match event::read()? {
+                Event::Key(key) => match app.mode {
+                    Mode::FenInput(_) => app.on_fen_key(key.code),
+                    Mode::Promotion { .. } => app.on_promotion_key(key.code),
+                    Mode::Normal => {
+                        if key.code == KeyCode::Char('q') {
+                            break;
+                        }
+                        app.on_key(key.code);
+                    }
+                },
+                Event::Mouse(mouse) => {
+                    if let (Mode::Normal, MouseEventKind::Down(MouseButton::Left)) =
+                        (&app.mode, mouse.kind)
+                    {
+                        app.on_click(mouse.column, mouse.row);
+                    }
                 }
-                app.on_key(key.code);
+                _ => {}
             }
         }
     }
     Ok(())
 }
 
+/// Install a panic hook that restores the terminal before the default hook
+/// prints the panic message, so a crash inside the TUI does not leave the
+/// terminal in raw mode on the alternate screen.
+fn install_panic_hook() {
+    let original_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
+        original_hook(info);
+    }));
+}
+
 fn main() -> anyhow::Result<()> {
+    install_panic_hook();
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;